@@ -2,17 +2,148 @@ use std::collections::HashMap;
 use std::fs::File;
 use std::io::{Read, Seek, SeekFrom};
 use std::sync::Mutex;
-use tauri::State;
+use tauri::http;
+use tauri::{Emitter, Manager, State};
 use uuid::Uuid;
 
+use chacha20::cipher::{KeyIvInit, StreamCipher, StreamCipherSeek};
+
+/// Default 96-bit nonce for the ChaCha20 keystream, used for archives without
+/// an integrity footer. An archive with a footer carries its own nonce there
+/// (see [`parse_archive_footer`]).
+const ARCHIVE_NONCE: [u8; 12] = *b"vitrine-chac";
+
+/// Size, in bytes, of the segments guarded by integrity tags.
+const SEGMENT_SIZE: u64 = 1 << 20; // 1 MiB
+
+/// Derive the 256-bit archive key from the build-time key via HKDF-SHA256,
+/// so the raw `VITRINE_ARCHIVE_KEY` material is never used as the cipher key
+/// directly. Returns `None` when the binary was built without a key.
+fn archive_key() -> Option<[u8; 32]> {
+    let key_hex = option_env!("VITRINE_ARCHIVE_KEY")?;
+    let ikm = hex::decode(key_hex).ok()?;
+    let hk = hkdf::Hkdf::<sha2::Sha256>::new(None, &ikm);
+    let mut key = [0u8; 32];
+    hk.expand(b"vitrine archive v1", &mut key).ok()?;
+    Some(key)
+}
+
+/// Decode `buf` in place if this binary was compiled with an archive key.
+/// `offset` is the absolute position of `buf[0]` in the file; ChaCha20 is a
+/// counter-mode stream cipher, so the keystream is seeked to `offset` —
+/// `block = offset / 64`, intra-block skip `offset % 64` — giving correct
+/// random access without decoding the whole file. `nonce` is the per-archive
+/// nonce taken from the integrity footer, or [`ARCHIVE_NONCE`] when the archive
+/// carries no footer.
+fn archive_decode(buf: &mut [u8], offset: u64, nonce: &[u8; 12]) {
+    if let Some(key) = archive_key() {
+        let mut cipher = chacha20::ChaCha20::new(&key.into(), nonce.into());
+        cipher.seek(offset);
+        cipher.apply_keystream(buf);
+    }
+}
+
+/// Verify the plaintext integrity of every [`SEGMENT_SIZE`] segment the byte
+/// range `[offset, offset + len)` touches against the SHA-256 tags from the
+/// archive footer. Each segment is re-read and decoded in full — reads are not
+/// segment-aligned, so the window hashed for a tag must be the whole segment,
+/// not whatever slice the caller asked for. A tampered or truncated archive
+/// fails loudly instead of silently decoding to garbage. Seeks `file`; callers
+/// that read sequentially must restore the cursor afterward.
+fn verify_range(
+    file: &mut File,
+    tags: &[[u8; 32]],
+    nonce: &[u8; 12],
+    data_size: u64,
+    offset: u64,
+    len: u64,
+) -> Result<(), String> {
+    use sha2::Digest;
+    if len == 0 {
+        return Ok(());
+    }
+    let first = offset / SEGMENT_SIZE;
+    let last = (offset + len - 1) / SEGMENT_SIZE;
+    for seg in first..=last {
+        let expected = tags
+            .get(seg as usize)
+            .ok_or_else(|| format!("Missing integrity tag for segment {}", seg))?;
+        let seg_start = seg * SEGMENT_SIZE;
+        if seg_start >= data_size {
+            break;
+        }
+        let seg_len = SEGMENT_SIZE.min(data_size - seg_start) as usize;
+        file.seek(SeekFrom::Start(seg_start)).map_err(|e| e.to_string())?;
+        let mut seg_buf = vec![0u8; seg_len];
+        file.read_exact(&mut seg_buf).map_err(|e| e.to_string())?;
+        archive_decode(&mut seg_buf, seg_start, nonce);
+        let got = sha2::Sha256::digest(&seg_buf);
+        if got.as_slice() != expected.as_slice() {
+            return Err(format!("Integrity check failed for segment {}", seg));
+        }
+    }
+    Ok(())
+}
+
+/// Parse the trailing integrity footer of an archive, if present. The footer is
+/// laid out as `[tag; n][nonce: 12][n: u32 LE][magic: "VITF"]` at end-of-file;
+/// returns the plaintext `data_size` (file bytes minus footer), the per-archive
+/// nonce, and the per-segment tags. A file without the magic trailer carries no
+/// integrity: the whole file is data and the constant nonce is used. A footer
+/// whose length or segment count is inconsistent with the file is rejected so a
+/// truncated archive fails at open time.
+fn parse_archive_footer(
+    file: &mut File,
+    file_size: u64,
+) -> Result<(u64, [u8; 12], Option<Vec<[u8; 32]>>), String> {
+    const MAGIC: &[u8; 4] = b"VITF";
+    const TAIL: u64 = 12 + 4 + 4; // nonce + segment count + magic
+    if file_size < TAIL {
+        return Ok((file_size, ARCHIVE_NONCE, None));
+    }
+    file.seek(SeekFrom::Start(file_size - TAIL)).map_err(|e| e.to_string())?;
+    let mut tail = [0u8; TAIL as usize];
+    file.read_exact(&mut tail).map_err(|e| e.to_string())?;
+    if &tail[16..20] != MAGIC {
+        return Ok((file_size, ARCHIVE_NONCE, None));
+    }
+    let mut nonce = [0u8; 12];
+    nonce.copy_from_slice(&tail[0..12]);
+    let count = u32::from_le_bytes(tail[12..16].try_into().unwrap()) as u64;
+    let footer_size = TAIL + count * 32;
+    if footer_size > file_size {
+        return Err("Archive integrity footer is truncated".into());
+    }
+    let data_size = file_size - footer_size;
+    let expected = data_size.div_ceil(SEGMENT_SIZE);
+    if count != expected {
+        return Err(format!(
+            "Archive integrity footer covers {} segments, expected {}",
+            count, expected
+        ));
+    }
+    file.seek(SeekFrom::Start(data_size)).map_err(|e| e.to_string())?;
+    let mut tags = vec![[0u8; 32]; count as usize];
+    for tag in tags.iter_mut() {
+        file.read_exact(tag).map_err(|e| e.to_string())?;
+    }
+    Ok((data_size, nonce, Some(tags)))
+}
+
 // =============================================================================
 // IPC FILE HANDLE STORE
 // =============================================================================
 
 struct FileHandle {
     file: File,
-    #[allow(dead_code)]
+    /// Plaintext size: the file length minus any integrity footer.
     size: u64,
+    /// Per-archive ChaCha20 nonce from the integrity footer, or the constant
+    /// [`ARCHIVE_NONCE`] for archives without a footer.
+    nonce: [u8; 12],
+    /// Per-segment SHA-256 tags from the archive footer, when this archive was
+    /// packaged with integrity enabled. `None` means no integrity checking.
+    integrity: Option<Vec<[u8; 32]>>,
 }
 
 #[derive(Default)]
@@ -20,26 +151,194 @@ struct FileHandleStore {
     handles: Mutex<HashMap<String, FileHandle>>,
 }
 
+// =============================================================================
+// FILESYSTEM SCOPE — allow/forbid patterns gating ipc_open_file
+// =============================================================================
+
+/// A filesystem access scope modeled on Tauri's `FsScope`: a requested path is
+/// permitted only if it matches an `allowed` glob and no `forbidden` glob.
+/// Forbidden patterns always take precedence. Starts empty, so nothing is
+/// readable until the app grants access (typically by seeding the directory
+/// the user picks through the dialog plugin).
+#[derive(Default)]
+struct FsScope {
+    allowed: Mutex<Vec<glob::Pattern>>,
+    forbidden: Mutex<Vec<glob::Pattern>>,
+}
+
+impl FsScope {
+    fn push(list: &Mutex<Vec<glob::Pattern>>, pattern: &str) -> Result<(), String> {
+        let pat = glob::Pattern::new(pattern).map_err(|e| e.to_string())?;
+        let mut guard = list.lock().unwrap();
+        if !guard.iter().any(|p| p.as_str() == pat.as_str()) {
+            guard.push(pat);
+        }
+        Ok(())
+    }
+
+    /// Glob patterns match path separators literally, so matching a file *and*
+    /// its descendants needs both the bare path and a trailing `/**`. The path
+    /// is a literal, so any `*`/`?`/`[...]` in it is escaped first — otherwise a
+    /// picked directory like `/data/a*b` would grant `/data/aEVILb/...`.
+    fn scope_patterns(path: &str, recursive: bool) -> Vec<String> {
+        let escaped = glob::Pattern::escape(path);
+        let mut pats = vec![escaped.clone()];
+        if recursive {
+            let sep = if escaped.ends_with('/') { "" } else { "/" };
+            pats.push(format!("{}{}**", escaped, sep));
+        }
+        pats
+    }
+
+    fn is_allowed(&self, path: &std::path::Path) -> bool {
+        let opts = glob::MatchOptions {
+            require_literal_separator: true,
+            ..Default::default()
+        };
+        if self
+            .forbidden
+            .lock()
+            .unwrap()
+            .iter()
+            .any(|p| p.matches_path_with(path, opts))
+        {
+            return false;
+        }
+        self.allowed
+            .lock()
+            .unwrap()
+            .iter()
+            .any(|p| p.matches_path_with(path, opts))
+    }
+}
+
+/// Canonicalize a scope path so patterns match the canonicalized path
+/// `ipc_open_file` checks. A path that cannot be resolved yet (e.g. a forbid
+/// rule for something not on disk) is kept exactly as given, not normalized.
+fn canonical_scope_path(path: &str) -> String {
+    std::fs::canonicalize(path)
+        .map(|p| p.to_string_lossy().into_owned())
+        .unwrap_or_else(|_| path.to_string())
+}
+
+/// Allow reads under `path`. When `recursive`, its whole subtree is granted.
+#[tauri::command]
+fn ipc_allow_directory(path: String, recursive: bool, scope: State<FsScope>) -> Result<(), String> {
+    for pat in FsScope::scope_patterns(&canonical_scope_path(&path), recursive) {
+        FsScope::push(&scope.allowed, &pat)?;
+    }
+    Ok(())
+}
+
+/// Allow reads of a single file.
+#[tauri::command]
+fn ipc_allow_file(path: String, scope: State<FsScope>) -> Result<(), String> {
+    for pat in FsScope::scope_patterns(&canonical_scope_path(&path), false) {
+        FsScope::push(&scope.allowed, &pat)?;
+    }
+    Ok(())
+}
+
+/// Forbid `path` (and, when `recursive`, its subtree). Takes precedence over
+/// any allow pattern.
+#[tauri::command]
+fn ipc_forbid_directory(
+    path: String,
+    recursive: bool,
+    scope: State<FsScope>,
+) -> Result<(), String> {
+    for pat in FsScope::scope_patterns(&canonical_scope_path(&path), recursive) {
+        FsScope::push(&scope.forbidden, &pat)?;
+    }
+    Ok(())
+}
+
+/// Forbid a single file. Takes precedence over any allow pattern.
+#[tauri::command]
+fn ipc_forbid_file(path: String, scope: State<FsScope>) -> Result<(), String> {
+    for pat in FsScope::scope_patterns(&canonical_scope_path(&path), false) {
+        FsScope::push(&scope.forbidden, &pat)?;
+    }
+    Ok(())
+}
+
+/// Prompt the user to pick a folder via the dialog plugin and, on selection,
+/// grant its subtree to the scope so the frontend can open assets inside it
+/// without any separate `ipc_allow_*` call. Returns the canonical path, or
+/// `None` if the picker was dismissed.
+#[tauri::command]
+fn ipc_pick_folder(app: tauri::AppHandle, scope: State<FsScope>) -> Result<Option<String>, String> {
+    use tauri_plugin_dialog::DialogExt;
+    let Some(picked) = app.dialog().file().blocking_pick_folder() else {
+        return Ok(None);
+    };
+    let path = picked.into_path().map_err(|e| e.to_string())?;
+    let canonical = canonical_scope_path(&path.to_string_lossy());
+    for pat in FsScope::scope_patterns(&canonical, true) {
+        FsScope::push(&scope.allowed, &pat)?;
+    }
+    Ok(Some(canonical))
+}
+
+/// Prompt the user to pick a single file via the dialog plugin and grant it to
+/// the scope. Returns the canonical path, or `None` if the picker was dismissed.
+#[tauri::command]
+fn ipc_pick_file(app: tauri::AppHandle, scope: State<FsScope>) -> Result<Option<String>, String> {
+    use tauri_plugin_dialog::DialogExt;
+    let Some(picked) = app.dialog().file().blocking_pick_file() else {
+        return Ok(None);
+    };
+    let path = picked.into_path().map_err(|e| e.to_string())?;
+    let canonical = canonical_scope_path(&path.to_string_lossy());
+    for pat in FsScope::scope_patterns(&canonical, false) {
+        FsScope::push(&scope.allowed, &pat)?;
+    }
+    Ok(Some(canonical))
+}
+
 // =============================================================================
 // IPC COMMANDS — byte-level random access to files on disk
 // =============================================================================
 
 /// Open a file and return a handle ID + file size.
 /// The handle stays open until ipc_close_file is called.
+/// The path is canonicalized and checked against the filesystem scope; paths
+/// outside the allowed set are rejected so a compromised frontend cannot read
+/// arbitrary files on disk.
 #[tauri::command]
-fn ipc_open_file(path: String, store: State<FileHandleStore>) -> Result<(String, u64), String> {
-    let file = File::open(&path).map_err(|e| format!("Failed to open {}: {}", path, e))?;
-    let size = file.metadata().map_err(|e| e.to_string())?.len();
+fn ipc_open_file(
+    path: String,
+    store: State<FileHandleStore>,
+    scope: State<FsScope>,
+    catalog: State<Catalog>,
+) -> Result<(String, u64), String> {
+    let canonical = std::fs::canonicalize(&path)
+        .map_err(|e| format!("Failed to resolve {}: {}", path, e))?;
+    if !scope.is_allowed(&canonical) {
+        return Err(format!("Path not in allowed scope: {}", canonical.display()));
+    }
+    let mut file = File::open(&canonical).map_err(|e| format!("Failed to open {}: {}", path, e))?;
+    let meta = file.metadata().map_err(|e| e.to_string())?;
+    let file_size = meta.len();
+    let (data_size, nonce, integrity) = parse_archive_footer(&mut file, file_size)?;
+    let canonical_str = canonical.to_string_lossy().into_owned();
+    catalog
+        .record_open(&canonical_str, file_size, mtime_secs(&meta), detect_format(&canonical_str))
+        .map_err(|e| e.to_string())?;
     let id = Uuid::new_v4().to_string();
-    store.handles.lock().unwrap().insert(id.clone(), FileHandle { file, size });
-    Ok((id, size))
+    store
+        .handles
+        .lock()
+        .unwrap()
+        .insert(id.clone(), FileHandle { file, size: data_size, nonce, integrity });
+    Ok((id, data_size))
 }
 
 /// Read `length` bytes starting at `offset` from an open file handle.
 /// Returns raw bytes via tauri::ipc::Response to avoid JSON serialization
 /// (Vec<u8> would be serialized as a JSON array of numbers, which for a 150MB
 /// file means ~600MB of JSON text — enough to crash the webview).
-/// If compiled with VITRINE_ARCHIVE_KEY, XOR-decodes the bytes on the fly.
+/// If compiled with VITRINE_ARCHIVE_KEY, decrypts the bytes on the fly.
 #[tauri::command]
 fn ipc_read_bytes(
     handle_id: String,
@@ -51,6 +350,17 @@ fn ipc_read_bytes(
     let entry = handles
         .get_mut(&handle_id)
         .ok_or_else(|| format!("Invalid file handle: {}", handle_id))?;
+    // Reject reads past the plaintext size so a read never runs off the end of
+    // the data region and into (and decodes) the trailing integrity footer.
+    let end = offset
+        .checked_add(length as u64)
+        .ok_or_else(|| "Read range overflows u64".to_string())?;
+    if end > entry.size {
+        return Err(format!(
+            "Read [{}, {}) past end of file ({} bytes)",
+            offset, end, entry.size
+        ));
+    }
     entry
         .file
         .seek(SeekFrom::Start(offset))
@@ -58,14 +368,11 @@ fn ipc_read_bytes(
     let mut buf = vec![0u8; length as usize];
     entry.file.read_exact(&mut buf).map_err(|e| e.to_string())?;
 
-    // XOR-decode if this binary was compiled with an archive encryption key
-    if let Some(key_hex) = option_env!("VITRINE_ARCHIVE_KEY") {
-        if let Ok(key) = hex::decode(key_hex) {
-            let key_len = key.len();
-            for (i, byte) in buf.iter_mut().enumerate() {
-                *byte ^= key[(offset as usize + i) % key_len];
-            }
-        }
+    // Decrypt if this binary was compiled with an archive encryption key
+    archive_decode(&mut buf, offset, &entry.nonce);
+    // Verify every segment the read touches against its footer tag.
+    if let Some(tags) = entry.integrity.as_deref() {
+        verify_range(&mut entry.file, tags, &entry.nonce, entry.size, offset, length as u64)?;
     }
 
     Ok(tauri::ipc::Response::new(buf))
@@ -78,6 +385,510 @@ fn ipc_close_file(handle_id: String, store: State<FileHandleStore>) -> Result<()
     Ok(())
 }
 
+// =============================================================================
+// STREAMING READS — emit decoded chunks as Tauri events with backpressure
+// =============================================================================
+
+/// Per-stream control channel. The background reader holds the receiving end
+/// of `ack` and blocks after each chunk until the frontend acks the previous
+/// one; dropping the sender (on cancel) also unblocks the reader so it exits.
+struct StreamControl {
+    ack: crossbeam_channel::Sender<u64>,
+    cancel: std::sync::Arc<std::sync::atomic::AtomicBool>,
+}
+
+#[derive(Default)]
+struct StreamStore {
+    streams: Mutex<HashMap<String, StreamControl>>,
+}
+
+/// Payload of a `splat://chunk` event: the decoded bytes (hex-encoded, since
+/// event payloads are JSON) plus enough metadata for the loader to place them.
+#[derive(serde::Serialize, Clone)]
+struct ChunkEvent {
+    stream_id: String,
+    handle_id: String,
+    seq: u64,
+    offset: u64,
+    data: String,
+}
+
+/// Payload of the terminal `splat://done` event, emitted only when the reader
+/// reached end-of-data cleanly.
+#[derive(serde::Serialize, Clone)]
+struct DoneEvent {
+    stream_id: String,
+    handle_id: String,
+    chunks: u64,
+}
+
+/// Payload of the terminal `splat://error` event, emitted when the reader
+/// aborts mid-stream (read failure or failed integrity check) so the frontend
+/// can distinguish a complete file from a truncated or tampered one.
+#[derive(serde::Serialize, Clone)]
+struct ErrorEvent {
+    stream_id: String,
+    handle_id: String,
+    seq: u64,
+    offset: u64,
+    message: String,
+}
+
+/// Stream a file to the frontend as a sequence of `splat://chunk` events
+/// followed by a `splat://done` event, or by a `splat://error` event when the
+/// read aborts. A background reader walks the file
+/// sequentially, applies archive decode, and emits each chunk, pausing until
+/// the frontend acks the previous chunk via `ipc_ack_chunk` so a 150MB asset
+/// can be parsed progressively without the whole buffer going resident.
+/// Returns the stream id used to ack and cancel.
+#[tauri::command]
+fn ipc_stream_file(
+    handle_id: String,
+    chunk_size: u32,
+    app: tauri::AppHandle,
+    store: State<FileHandleStore>,
+    streams: State<StreamStore>,
+) -> Result<String, String> {
+    if chunk_size == 0 {
+        return Err("chunk_size must be non-zero".into());
+    }
+    // Take a cloned file descriptor so the reader outlives the handle. The clone
+    // shares the handle's cursor, so the reader seeks and reads under the store
+    // lock, as ranged reads on the same handle do.
+    let (mut file, integrity, nonce, data_size) = {
+        let handles = store.handles.lock().unwrap();
+        let entry = handles
+            .get(&handle_id)
+            .ok_or_else(|| format!("Invalid file handle: {}", handle_id))?;
+        let file = entry.file.try_clone().map_err(|e| e.to_string())?;
+        (file, entry.integrity.clone(), entry.nonce, entry.size)
+    };
+
+    let stream_id = Uuid::new_v4().to_string();
+    let (ack_tx, ack_rx) = crossbeam_channel::bounded::<u64>(1);
+    let cancel = std::sync::Arc::new(std::sync::atomic::AtomicBool::new(false));
+    streams.streams.lock().unwrap().insert(
+        stream_id.clone(),
+        StreamControl { ack: ack_tx, cancel: cancel.clone() },
+    );
+
+    let reader_id = stream_id.clone();
+    std::thread::spawn(move || {
+        use std::sync::atomic::Ordering;
+
+        let mut offset: u64 = 0;
+        let mut seq: u64 = 0;
+        let mut buf = vec![0u8; chunk_size as usize];
+        // Reason the loop stopped before end-of-data, if any; drives whether the
+        // terminal event is `splat://done` or `splat://error`.
+        let mut error: Option<String> = None;
+        loop {
+            if cancel.load(Ordering::Relaxed) || offset >= data_size {
+                break;
+            }
+            // Read only the plaintext region, never the trailing footer.
+            let n = (data_size - offset).min(chunk_size as u64) as usize;
+            let chunk = &mut buf[..n];
+            let store = app.state::<FileHandleStore>();
+            let guard = store.handles.lock().unwrap();
+            if file.seek(SeekFrom::Start(offset)).is_err() || file.read_exact(chunk).is_err() {
+                error = Some(format!("Failed to read {} bytes at offset {}", n, offset));
+                break;
+            }
+            archive_decode(chunk, offset, &nonce);
+            if let Some(tags) = integrity.as_deref() {
+                if let Err(e) = verify_range(&mut file, tags, &nonce, data_size, offset, n as u64) {
+                    error = Some(e);
+                    break;
+                }
+            }
+            drop(guard);
+            let event = ChunkEvent {
+                stream_id: reader_id.clone(),
+                handle_id: handle_id.clone(),
+                seq,
+                offset,
+                data: hex::encode(&chunk[..]),
+            };
+            if app.emit("splat://chunk", event).is_err() {
+                break;
+            }
+            // Backpressure: wait for the ack that matches this chunk, ignoring
+            // stale or duplicate acks that would otherwise advance the reader.
+            // An `Err` means the sender was dropped (cancel) — stop.
+            let mut acked = false;
+            loop {
+                match ack_rx.recv() {
+                    Ok(s) if s == seq => {
+                        acked = true;
+                        break;
+                    }
+                    Ok(_) => continue,
+                    Err(_) => break,
+                }
+            }
+            if !acked {
+                break;
+            }
+            offset += n as u64;
+            seq += 1;
+        }
+        if !cancel.load(Ordering::Relaxed) {
+            if let Some(message) = error {
+                let _ = app.emit(
+                    "splat://error",
+                    ErrorEvent { stream_id: reader_id.clone(), handle_id, seq, offset, message },
+                );
+            } else if offset >= data_size {
+                let _ = app.emit(
+                    "splat://done",
+                    DoneEvent { stream_id: reader_id.clone(), handle_id, chunks: seq },
+                );
+            }
+        }
+        app.state::<StreamStore>()
+            .streams
+            .lock()
+            .unwrap()
+            .remove(&reader_id);
+    });
+
+    Ok(stream_id)
+}
+
+/// Acknowledge receipt of a chunk so the reader may emit the next one.
+#[tauri::command]
+fn ipc_ack_chunk(stream_id: String, seq: u64, streams: State<StreamStore>) -> Result<(), String> {
+    let guard = streams.streams.lock().unwrap();
+    if let Some(control) = guard.get(&stream_id) {
+        // A full channel just means the reader hasn't parked yet; drop the ack.
+        let _ = control.ack.try_send(seq);
+    }
+    Ok(())
+}
+
+/// Cancel a running stream and free its file handle.
+#[tauri::command]
+fn ipc_cancel_stream(
+    stream_id: String,
+    handle_id: String,
+    streams: State<StreamStore>,
+    store: State<FileHandleStore>,
+) -> Result<(), String> {
+    if let Some(control) = streams.streams.lock().unwrap().remove(&stream_id) {
+        control
+            .cancel
+            .store(true, std::sync::atomic::Ordering::Relaxed);
+        // Drop the sender so a parked reader's recv() returns immediately.
+        drop(control);
+    }
+    store.handles.lock().unwrap().remove(&handle_id);
+    Ok(())
+}
+
+// =============================================================================
+// FILE CATALOG — persistent index of opened files (rusqlite)
+// =============================================================================
+
+/// Persistent catalog of every file opened through `ipc_open_file`. Backed by
+/// a SQLite database in the app data directory with a `migrations` table so
+/// the schema can evolve across releases.
+struct Catalog {
+    conn: Mutex<rusqlite::Connection>,
+}
+
+/// A catalog record for one file, as returned to the frontend.
+#[derive(serde::Serialize, Clone)]
+struct FileRecord {
+    path: String,
+    size: u64,
+    /// Unix seconds the file was last opened.
+    last_opened: i64,
+    format: String,
+    /// Cached SHA-256 of the on-disk bytes, `None` until first computed.
+    sha256: Option<String>,
+}
+
+/// Ordered schema migrations; the index + 1 is the stored version number.
+const MIGRATIONS: &[&str] = &[
+    "CREATE TABLE files (
+    path        TEXT PRIMARY KEY,
+    size        INTEGER NOT NULL,
+    last_opened INTEGER NOT NULL,
+    format      TEXT NOT NULL,
+    sha256      TEXT
+);",
+    "ALTER TABLE files ADD COLUMN mtime INTEGER NOT NULL DEFAULT 0;",
+];
+
+impl Catalog {
+    fn open(path: &std::path::Path) -> rusqlite::Result<Self> {
+        let conn = rusqlite::Connection::open(path)?;
+        conn.execute_batch(
+            "CREATE TABLE IF NOT EXISTS migrations (
+                version    INTEGER PRIMARY KEY,
+                applied_at INTEGER NOT NULL
+            );",
+        )?;
+        let current: i64 = conn
+            .query_row("SELECT COALESCE(MAX(version), 0) FROM migrations", [], |r| r.get(0))?;
+        for (i, stmt) in MIGRATIONS.iter().enumerate() {
+            let version = i as i64 + 1;
+            if version > current {
+                conn.execute_batch(stmt)?;
+                conn.execute(
+                    "INSERT INTO migrations (version, applied_at) VALUES (?1, ?2)",
+                    rusqlite::params![version, now_secs()],
+                )?;
+            }
+        }
+        Ok(Catalog { conn: Mutex::new(conn) })
+    }
+
+    /// Record (or refresh) an opened file, leaving the checksum for lazy
+    /// computation by `ipc_file_info`. The cached `sha256` is dropped whenever
+    /// the size *or* the on-disk mtime changes, so an in-place re-export that
+    /// keeps the same length still invalidates the stale checksum.
+    fn record_open(&self, path: &str, size: u64, mtime: i64, format: &str) -> rusqlite::Result<()> {
+        self.conn.lock().unwrap().execute(
+            "INSERT INTO files (path, size, mtime, last_opened, format)
+             VALUES (?1, ?2, ?3, ?4, ?5)
+             ON CONFLICT(path) DO UPDATE SET
+                size = excluded.size,
+                mtime = excluded.mtime,
+                last_opened = excluded.last_opened,
+                format = excluded.format,
+                sha256 = CASE
+                    WHEN files.size = excluded.size AND files.mtime = excluded.mtime
+                    THEN files.sha256 ELSE NULL END",
+            rusqlite::params![path, size, mtime, now_secs(), format],
+        )?;
+        Ok(())
+    }
+}
+
+/// A file's last-modified time in Unix seconds, or 0 when the platform does
+/// not report one.
+fn mtime_secs(meta: &std::fs::Metadata) -> i64 {
+    meta.modified()
+        .ok()
+        .and_then(|t| t.duration_since(std::time::UNIX_EPOCH).ok())
+        .map(|d| d.as_secs() as i64)
+        .unwrap_or(0)
+}
+
+/// Current time in Unix seconds.
+fn now_secs() -> i64 {
+    std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .map(|d| d.as_secs() as i64)
+        .unwrap_or(0)
+}
+
+/// Detect an asset format from a path's extension.
+fn detect_format(path: &str) -> &'static str {
+    match path.rsplit('.').next().map(str::to_ascii_lowercase).as_deref() {
+        Some("glb") => "glb",
+        Some("gltf") => "gltf",
+        Some("splat") => "splat",
+        Some("ply") => "ply",
+        _ => "unknown",
+    }
+}
+
+/// SHA-256 the on-disk bytes of `path`, streaming so a 150MB asset does not
+/// balloon memory. Hashes the ciphertext exactly as packaged on disk.
+fn checksum_file(path: &str) -> Result<String, String> {
+    use sha2::Digest;
+    let mut file = File::open(path).map_err(|e| e.to_string())?;
+    let mut hasher = sha2::Sha256::new();
+    let mut buf = vec![0u8; 1 << 20];
+    loop {
+        let n = file.read(&mut buf).map_err(|e| e.to_string())?;
+        if n == 0 {
+            break;
+        }
+        hasher.update(&buf[..n]);
+    }
+    Ok(hex::encode(hasher.finalize()))
+}
+
+/// Return the cached catalog record for `path`, computing and storing the
+/// SHA-256 checksum lazily on first request. The checksum lets the viewer tell
+/// when a previously-loaded asset has changed on disk.
+#[tauri::command]
+fn ipc_file_info(path: String, catalog: State<Catalog>) -> Result<FileRecord, String> {
+    let mut record = {
+        let conn = catalog.conn.lock().unwrap();
+        conn.query_row(
+            "SELECT path, size, last_opened, format, sha256 FROM files WHERE path = ?1",
+            rusqlite::params![path],
+            |row| {
+                Ok(FileRecord {
+                    path: row.get(0)?,
+                    size: row.get::<_, i64>(1)? as u64,
+                    last_opened: row.get(2)?,
+                    format: row.get(3)?,
+                    sha256: row.get(4)?,
+                })
+            },
+        )
+        .map_err(|_| format!("No catalog entry for {}", path))?
+    };
+
+    if record.sha256.is_none() {
+        let sum = checksum_file(&record.path)?;
+        catalog
+            .conn
+            .lock()
+            .unwrap()
+            .execute(
+                "UPDATE files SET sha256 = ?1 WHERE path = ?2",
+                rusqlite::params![sum, record.path],
+            )
+            .map_err(|e| e.to_string())?;
+        record.sha256 = Some(sum);
+    }
+    Ok(record)
+}
+
+/// Return recently opened files, most-recent first, for a recents list.
+#[tauri::command]
+fn ipc_recent_files(catalog: State<Catalog>) -> Result<Vec<FileRecord>, String> {
+    let conn = catalog.conn.lock().unwrap();
+    let mut stmt = conn
+        .prepare(
+            "SELECT path, size, last_opened, format, sha256 FROM files
+             ORDER BY last_opened DESC LIMIT 50",
+        )
+        .map_err(|e| e.to_string())?;
+    let rows = stmt
+        .query_map([], |row| {
+            Ok(FileRecord {
+                path: row.get(0)?,
+                size: row.get::<_, i64>(1)? as u64,
+                last_opened: row.get(2)?,
+                format: row.get(3)?,
+                sha256: row.get(4)?,
+            })
+        })
+        .map_err(|e| e.to_string())?;
+    rows.collect::<rusqlite::Result<Vec<_>>>()
+        .map_err(|e| e.to_string())
+}
+
+// =============================================================================
+// CUSTOM URI SCHEME — splat://<handle_id>/... ranged streaming
+// =============================================================================
+
+/// Pick a Content-Type from a path's extension, matching the formats the
+/// frontend loaders understand.
+fn content_type_for(path: &str) -> &'static str {
+    match path.rsplit('.').next().map(str::to_ascii_lowercase).as_deref() {
+        Some("glb") => "model/gltf-binary",
+        Some("gltf") => "model/gltf+json",
+        _ => "application/octet-stream",
+    }
+}
+
+/// Parse a single-range `Range` header into an inclusive `(start, end)` pair
+/// clamped to `size`. Supports `bytes=start-end`, the open-ended `bytes=start-`,
+/// and the suffix form `bytes=-N` (the last `N` bytes). Returns `None` for an
+/// absent, malformed, multi-range, or unsatisfiable header; the caller serves
+/// the whole file with `200 OK` when the header is absent and answers
+/// `416 Range Not Satisfiable` otherwise.
+fn parse_range(header: Option<&str>, size: u64) -> Option<(u64, u64)> {
+    let spec = header?.strip_prefix("bytes=")?;
+    if spec.contains(',') {
+        return None;
+    }
+    let (start, end) = spec.split_once('-')?;
+    let (start, end) = (start.trim(), end.trim());
+    if start.is_empty() {
+        // Suffix range: the last N bytes. N == 0 (or an empty file) is
+        // unsatisfiable.
+        let n: u64 = end.parse().ok()?;
+        if n == 0 || size == 0 {
+            return None;
+        }
+        return Some((size - n.min(size), size - 1));
+    }
+    let start: u64 = start.parse().ok()?;
+    let end = match end {
+        "" => size.saturating_sub(1),
+        e => e.parse::<u64>().ok()?.min(size.saturating_sub(1)),
+    };
+    if start > end || start >= size {
+        return None;
+    }
+    Some((start, end))
+}
+
+/// Serve a ranged read from an open handle as an HTTP response, decoding the
+/// sliced range exactly as `ipc_read_bytes` does. Reuses the existing
+/// `FileHandleStore` so the webview's `fetch`/`<img>`/loader code can stream
+/// large files natively instead of paying an IPC round-trip per chunk.
+fn serve_splat(store: &FileHandleStore, uri: &str, range: Option<&str>) -> http::Response<Vec<u8>> {
+    let bad = |status: u16| {
+        http::Response::builder()
+            .status(status)
+            .body(Vec::new())
+            .unwrap()
+    };
+
+    // splat://<handle_id>/<name> — the authority is the handle id.
+    let Some(rest) = uri.strip_prefix("splat://") else {
+        return bad(400);
+    };
+    // Drop any `?query`/`#fragment` before splitting out the authority and the
+    // name, so extension sniffing below sees `model.glb`, not `model.glb?v=2`.
+    let path = rest.split(['?', '#']).next().unwrap_or(rest);
+    let handle_id = path.split('/').next().unwrap_or("");
+
+    let mut handles = store.handles.lock().unwrap();
+    let Some(entry) = handles.get_mut(handle_id) else {
+        return bad(404);
+    };
+    let size = entry.size;
+
+    let (start, end, status) = match parse_range(range, size) {
+        Some((s, e)) => (s, e, 206),
+        None if range.is_some() => return bad(416),
+        None => (0, size.saturating_sub(1), 200),
+    };
+    // A whole-file response of an empty file carries no bytes.
+    let length = if status == 200 { size } else { end - start + 1 };
+
+    if entry.file.seek(SeekFrom::Start(start)).is_err() {
+        return bad(500);
+    }
+    let mut buf = vec![0u8; length as usize];
+    if entry.file.read_exact(&mut buf).is_err() {
+        return bad(500);
+    }
+    archive_decode(&mut buf, start, &entry.nonce);
+    // Verify integrity exactly as ipc_read_bytes does, so the protocol path
+    // never serves unverified bytes from a tampered archive.
+    if let Some(tags) = entry.integrity.as_deref() {
+        if verify_range(&mut entry.file, tags, &entry.nonce, size, start, length).is_err() {
+            return bad(500);
+        }
+    }
+
+    let mut builder = http::Response::builder()
+        .status(status)
+        .header(http::header::ACCEPT_RANGES, "bytes")
+        .header(http::header::CONTENT_TYPE, content_type_for(path))
+        .header(http::header::CONTENT_LENGTH, length);
+    if status == 206 {
+        builder = builder.header(
+            http::header::CONTENT_RANGE,
+            format!("bytes {}-{}/{}", start, end, size),
+        );
+    }
+    builder.body(buf).unwrap()
+}
+
 // =============================================================================
 // APP ENTRY
 // =============================================================================
@@ -86,10 +897,38 @@ fn ipc_close_file(handle_id: String, store: State<FileHandleStore>) -> Result<()
 pub fn run() {
     tauri::Builder::default()
         .manage(FileHandleStore::default())
+        .manage(FsScope::default())
+        .manage(StreamStore::default())
+        .setup(|app| {
+            let dir = app.path().app_data_dir()?;
+            std::fs::create_dir_all(&dir)?;
+            let catalog = Catalog::open(&dir.join("catalog.db"))?;
+            app.manage(catalog);
+            Ok(())
+        })
+        .register_uri_scheme_protocol("splat", |ctx, request| {
+            let store = ctx.app_handle().state::<FileHandleStore>();
+            let range = request
+                .headers()
+                .get(http::header::RANGE)
+                .and_then(|v| v.to_str().ok());
+            serve_splat(&store, &request.uri().to_string(), range)
+        })
         .invoke_handler(tauri::generate_handler![
             ipc_open_file,
             ipc_read_bytes,
-            ipc_close_file
+            ipc_close_file,
+            ipc_allow_directory,
+            ipc_allow_file,
+            ipc_forbid_directory,
+            ipc_forbid_file,
+            ipc_pick_folder,
+            ipc_pick_file,
+            ipc_stream_file,
+            ipc_ack_chunk,
+            ipc_cancel_stream,
+            ipc_file_info,
+            ipc_recent_files
         ])
         .plugin(tauri_plugin_dialog::init())
         .plugin(tauri_plugin_fs::init())